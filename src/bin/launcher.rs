@@ -7,17 +7,22 @@
 //! 2. Services continue running even when PC is off
 //! 3. Ctrl+C exits the launcher but KEEPS services running on cloud
 //! 4. Use `cargo run -- --stop` to stop cloud services
+//!
+//! The target pod is read from `HALLDYLL_RUNPOD_HOST`, `HALLDYLL_RUNPOD_PORT`,
+//! `HALLDYLL_RUNPOD_USER`, `HALLDYLL_RUNPOD_SSH_KEY` and `HALLDYLL_RUNPOD_URL`,
+//! and `--host`/`--port`/`--url` flags override the environment. The default
+//! proxy URL is only used for the default pod.
 
 use std::io::{BufRead, BufReader};
 use std::process::{Command, ExitCode, Stdio};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-/// `RunPod` SSH configuration.
-const RUNPOD_HOST: &str = "213.173.108.10";
-const RUNPOD_PORT: &str = "11842";
-const RUNPOD_USER: &str = "root";
-const RUNPOD_URL: &str = "https://zcgzoso3znn9kl-3000.proxy.runpod.net";
+/// Default `RunPod` SSH configuration.
+const DEFAULT_RUNPOD_HOST: &str = "213.173.108.10";
+const DEFAULT_RUNPOD_PORT: &str = "11842";
+const DEFAULT_RUNPOD_USER: &str = "root";
+const DEFAULT_RUNPOD_URL: &str = "https://zcgzoso3znn9kl-3000.proxy.runpod.net";
 
 /// Commands to deploy and start services on `RunPod`.
 const STARTUP_COMMANDS: &str = r#"
@@ -31,17 +36,102 @@ sleep 1 && \
 nohup ollama serve > /tmp/ollama.log 2>&1 & \
 sleep 3 && \
 echo "=== Starting Halldyll Server ===" && \
-HALLDYLL_PORT=3000 nohup ./target/release/halldyll-server > /tmp/halldyll.log 2>&1 & \
-sleep 2 && \
+HALLDYLL_PORT=3000 HALLDYLL_WARM_UP=1 nohup ./target/release/halldyll-server > /tmp/halldyll.log 2>&1 & \
+ready=0; \
+for i in $(seq 1 90); do curl -sf http://127.0.0.1:3000/ready > /dev/null && ready=1 && break; sleep 2; done; \
+if [ "$ready" = 1 ]; then \
 echo "Server started" && \
-echo "listening on 0.0.0.0:3000"
+echo "listening on 0.0.0.0:3000"; \
+else \
+echo "server not ready, see /tmp/halldyll.log"; \
+exit 1; \
+fi
 "#;
 
+/// Line printed by `STARTUP_COMMANDS` once `/ready` succeeds.
+const READY_MARKER: &str = "listening on";
+
+/// Line printed by `STARTUP_COMMANDS` when `/ready` never succeeds.
+const NOT_READY_MARKER: &str = "server not ready";
+
 /// Commands to stop services on `RunPod`.
 const CLEANUP_COMMANDS: &str = "pkill ollama 2>/dev/null || true; pkill halldyll-server 2>/dev/null || true";
 
-/// Get SSH key path.
-fn get_ssh_key() -> String {
+/// `RunPod` connection settings.
+struct RunpodConfig {
+    host: String,
+    port: u16,
+    user: String,
+    ssh_key: String,
+    url: Option<String>,
+}
+
+impl RunpodConfig {
+    /// Load settings from the environment, then apply `--host`/`--port`/`--url` flags.
+    fn load(args: &[String]) -> Result<Self, String> {
+        let env_or =
+            |name: &str, default: &str| std::env::var(name).unwrap_or_else(|_| default.to_string());
+
+        let mut host = env_or("HALLDYLL_RUNPOD_HOST", DEFAULT_RUNPOD_HOST);
+        let mut port = env_or("HALLDYLL_RUNPOD_PORT", DEFAULT_RUNPOD_PORT);
+        let mut url = env_or("HALLDYLL_RUNPOD_URL", "");
+
+        let mut iter = args.iter().skip(1);
+        while let Some(arg) = iter.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) => (flag, Some(value.to_string())),
+                None => (arg.as_str(), None),
+            };
+            let target = match flag {
+                "--host" => &mut host,
+                "--port" => &mut port,
+                "--url" => &mut url,
+                _ => continue,
+            };
+            *target = match inline {
+                Some(value) => value,
+                None => iter
+                    .next()
+                    .cloned()
+                    .ok_or_else(|| format!("{flag} requires a value"))?,
+            };
+        }
+
+        // Never show the default pod's proxy URL for another pod.
+        let url = if !url.is_empty() {
+            Some(url)
+        } else if host == DEFAULT_RUNPOD_HOST && port == DEFAULT_RUNPOD_PORT {
+            Some(DEFAULT_RUNPOD_URL.to_string())
+        } else {
+            None
+        };
+
+        let port = port
+            .parse()
+            .map_err(|_| format!("invalid SSH port: {port}"))?;
+
+        Ok(Self {
+            host,
+            port,
+            user: env_or("HALLDYLL_RUNPOD_USER", DEFAULT_RUNPOD_USER),
+            ssh_key: std::env::var("HALLDYLL_RUNPOD_SSH_KEY").unwrap_or_else(|_| default_ssh_key()),
+            url,
+        })
+    }
+
+    /// Where users reach the server: the proxy URL, or the host when it is unknown.
+    fn access_address(&self) -> String {
+        self.url.clone().unwrap_or_else(|| {
+            format!(
+                "{} (pass --url or set HALLDYLL_RUNPOD_URL for the proxy URL)",
+                self.host
+            )
+        })
+    }
+}
+
+/// Get default SSH key path.
+fn default_ssh_key() -> String {
     let home = std::env::var("USERPROFILE")
         .or_else(|_| std::env::var("HOME"))
         .unwrap_or_else(|_| ".".to_string());
@@ -49,7 +139,7 @@ fn get_ssh_key() -> String {
 }
 
 /// Build SSH command arguments.
-fn ssh_args(ssh_key: &str) -> Vec<String> {
+fn ssh_args(config: &RunpodConfig) -> Vec<String> {
     vec![
         "-o".to_string(),
         "StrictHostKeyChecking=no".to_string(),
@@ -58,18 +148,18 @@ fn ssh_args(ssh_key: &str) -> Vec<String> {
         "-o".to_string(),
         "ServerAliveCountMax=3".to_string(),
         "-p".to_string(),
-        RUNPOD_PORT.to_string(),
+        config.port.to_string(),
         "-i".to_string(),
-        ssh_key.to_string(),
-        format!("{RUNPOD_USER}@{RUNPOD_HOST}"),
+        config.ssh_key.clone(),
+        format!("{}@{}", config.user, config.host),
     ]
 }
 
 /// Deploy to `RunPod` via SSH.
-fn deploy_to_runpod(ssh_key: &str, shutdown_flag: &Arc<AtomicBool>) -> Result<(), String> {
-    println!("  Connecting to {RUNPOD_HOST}:{RUNPOD_PORT}...");
+fn deploy_to_runpod(config: &RunpodConfig, shutdown_flag: &Arc<AtomicBool>) -> Result<(), String> {
+    println!("  Connecting to {}:{}...", config.host, config.port);
 
-    let mut args = ssh_args(ssh_key);
+    let mut args = ssh_args(config);
     args.push(STARTUP_COMMANDS.to_string());
 
     let mut child = Command::new("ssh")
@@ -79,15 +169,20 @@ fn deploy_to_runpod(ssh_key: &str, shutdown_flag: &Arc<AtomicBool>) -> Result<()
         .spawn()
         .map_err(|e| format!("SSH failed: {e}"))?;
 
+    let mut ready = false;
     if let Some(stdout) = child.stdout.take() {
         let reader = BufReader::new(stdout);
         for line in reader.lines() {
             if shutdown_flag.load(Ordering::Relaxed) {
-                break;
+                return Ok(());
             }
             if let Ok(line) = line {
                 println!("  {line}");
-                if line.contains("listening on") {
+                if line.contains(READY_MARKER) {
+                    ready = true;
+                    break;
+                }
+                if line.contains(NOT_READY_MARKER) {
                     break;
                 }
             }
@@ -95,14 +190,18 @@ fn deploy_to_runpod(ssh_key: &str, shutdown_flag: &Arc<AtomicBool>) -> Result<()
     }
 
     drop(child);
-    Ok(())
+    if ready || shutdown_flag.load(Ordering::Relaxed) {
+        Ok(())
+    } else {
+        Err("server did not become ready (Ollama down or model missing?)".to_string())
+    }
 }
 
 /// Stop services on `RunPod`.
-fn cleanup_runpod(ssh_key: &str) {
-    println!("  Stopping services...");
+fn cleanup_runpod(config: &RunpodConfig) {
+    println!("  Stopping services on {}...", config.host);
 
-    let mut args = ssh_args(ssh_key);
+    let mut args = ssh_args(config);
     args.push(CLEANUP_COMMANDS.to_string());
 
     let _ = Command::new("ssh")
@@ -124,11 +223,22 @@ fn wait_for_interrupt(shutdown_flag: &Arc<AtomicBool>) {
 fn main() -> ExitCode {
     let args: Vec<String> = std::env::args().collect();
 
+    let config = match RunpodConfig::load(&args) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("  Invalid configuration: {e}");
+            eprintln!(
+                "  Usage: halldyll [--stop] [--host <host>] [--port <ssh-port>] [--url <proxy-url>]"
+            );
+            return ExitCode::from(2);
+        }
+    };
+
     // Check for --stop flag
     if args.iter().any(|a| a == "--stop") {
         println!();
         println!("  Stopping cloud services...");
-        cleanup_runpod(&get_ssh_key());
+        cleanup_runpod(&config);
         println!();
         return ExitCode::SUCCESS;
     }
@@ -139,7 +249,6 @@ fn main() -> ExitCode {
     println!("  ╚═══════════════════════════════════════════╝");
     println!();
 
-    let ssh_key = get_ssh_key();
     let shutdown_flag = Arc::new(AtomicBool::new(false));
     let shutdown_flag_handler = Arc::clone(&shutdown_flag);
 
@@ -157,7 +266,7 @@ fn main() -> ExitCode {
     println!("  Deploying to RunPod...");
     println!();
 
-    if let Err(e) = deploy_to_runpod(&ssh_key, &shutdown_flag) {
+    if let Err(e) = deploy_to_runpod(&config, &shutdown_flag) {
         eprintln!("  Deploy failed: {e}");
         return ExitCode::from(1);
     }
@@ -170,7 +279,7 @@ fn main() -> ExitCode {
     println!();
     println!("  ═══════════════════════════════════════════");
     println!("  Server running at:");
-    println!("  {RUNPOD_URL}");
+    println!("  {}", config.access_address());
     println!();
     println!("  Services will keep running on the cloud.");
     println!("  You can close this terminal safely.");
@@ -185,8 +294,68 @@ fn main() -> ExitCode {
     // Don't cleanup - services keep running on cloud
     println!();
     println!("  Launcher exited. Cloud services still running.");
-    println!("  Access at: {RUNPOD_URL}");
+    println!("  Access at: {}", config.access_address());
     println!();
 
     ExitCode::SUCCESS
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        std::iter::once("halldyll")
+            .chain(list.iter().copied())
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn load_accepts_inline_and_separate_values() {
+        let flags = args(&["--host=pod.example", "--port", "2222"]);
+        let Ok(config) = RunpodConfig::load(&flags) else {
+            panic!("valid flags were rejected");
+        };
+        assert_eq!(config.host, "pod.example");
+        assert_eq!(config.port, 2222);
+    }
+
+    #[test]
+    fn load_keeps_default_url_for_default_pod_only() {
+        let Ok(default_pod) = RunpodConfig::load(&args(&[])) else {
+            panic!("defaults were rejected");
+        };
+        assert_eq!(default_pod.url.as_deref(), Some(DEFAULT_RUNPOD_URL));
+
+        let Ok(other_pod) = RunpodConfig::load(&args(&["--host", "1.2.3.4"])) else {
+            panic!("valid flags were rejected");
+        };
+        assert_eq!(other_pod.url, None);
+        assert!(other_pod.access_address().starts_with("1.2.3.4"));
+
+        let flags = args(&[
+            "--host",
+            "1.2.3.4",
+            "--url=https://pod-3000.proxy.runpod.net",
+        ]);
+        let Ok(with_url) = RunpodConfig::load(&flags) else {
+            panic!("valid flags were rejected");
+        };
+        assert_eq!(
+            with_url.url.as_deref(),
+            Some("https://pod-3000.proxy.runpod.net")
+        );
+    }
+
+    #[test]
+    fn load_rejects_missing_value() {
+        let result = RunpodConfig::load(&args(&["--port"]));
+        assert!(matches!(result, Err(ref e) if e == "--port requires a value"));
+    }
+
+    #[test]
+    fn load_rejects_invalid_port() {
+        assert!(RunpodConfig::load(&args(&["--port=ssh"])).is_err());
+    }
+}
@@ -1,16 +1,25 @@
 //! Ollama client for cloud-based LLM inference.
 //!
-//! Connects to a remote Ollama server via HTTP API.
+//! Connects to a remote Ollama server via HTTP API, with a blocking
+//! ([`OllamaMinistral`]) and an async ([`AsyncOllamaMinistral`]) client.
 //! No local Ollama management - server runs on `RunPod`.
 
-use reqwest::blocking::Client;
+use reqwest::blocking::{Client, RequestBuilder, Response};
 use serde::{Deserialize, Serialize};
 use std::fmt;
-use std::time::Duration;
+use std::io::{BufRead, BufReader};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
 
 /// Environment variable for Ollama URL.
 const OLLAMA_URL_ENV: &str = "HALLDYLL_OLLAMA_URL";
 
+/// Environment variable overriding the number of GPU-offloaded layers.
+const NUM_GPU_ENV: &str = "HALLDYLL_NUM_GPU";
+
+/// Environment variable selecting the main GPU index.
+const MAIN_GPU_ENV: &str = "HALLDYLL_MAIN_GPU";
+
 /// Default Ollama URL (localhost fallback).
 const DEFAULT_OLLAMA_URL: &str = "http://127.0.0.1:11434";
 
@@ -28,13 +37,32 @@ const DEFAULT_NUM_THREAD: u32 = 8;
 
 /// HTTP timeouts.
 const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
-const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+const REQUEST_TIMEOUT: Duration = Duration::from_mins(2);
+
+/// Readiness probing.
+const READY_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const READY_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Get Ollama base URL from environment.
 fn get_ollama_url() -> String {
     std::env::var(OLLAMA_URL_ENV).unwrap_or_else(|_| DEFAULT_OLLAMA_URL.to_string())
 }
 
+/// Read an optional `u32` from the environment.
+fn env_u32(name: &str) -> Option<u32> {
+    std::env::var(name).ok().and_then(|v| v.trim().parse().ok())
+}
+
+/// Number of layers to offload to the GPU, from `HALLDYLL_NUM_GPU`.
+///
+/// `0` forces CPU-only inference. Returns `None` to let Ollama decide, since
+/// the local environment says nothing about the GPUs of a remote server.
+#[must_use]
+pub fn detect_gpu_layers() -> Option<u32> {
+    env_u32(NUM_GPU_ENV)
+}
+
 /// Errors from Ollama client.
 #[derive(Debug)]
 pub enum OllamaStarterError {
@@ -44,6 +72,16 @@ pub enum OllamaStarterError {
     HttpStatusNotOk(u16),
     /// Malformed response.
     HttpMalformedResponse,
+    /// Reading a streamed response failed.
+    StreamRead(std::io::Error),
+    /// Server did not become ready in time.
+    ServerNotReady,
+    /// Model is not installed on the server.
+    ModelNotFound(String),
+    /// Generation was cancelled by the caller.
+    Cancelled,
+    /// Ollama reported an error in the middle of a stream.
+    Server(String),
 }
 
 impl From<reqwest::Error> for OllamaStarterError {
@@ -58,19 +96,121 @@ impl fmt::Display for OllamaStarterError {
             Self::HttpClient(err) => write!(f, "http client error: {err}"),
             Self::HttpStatusNotOk(status) => write!(f, "ollama http status: {status}"),
             Self::HttpMalformedResponse => write!(f, "malformed response"),
+            Self::StreamRead(err) => write!(f, "stream read error: {err}"),
+            Self::ServerNotReady => write!(f, "ollama server not ready"),
+            Self::ModelNotFound(model) => write!(
+                f,
+                "model not installed: {model} (run `ollama pull {model}`)"
+            ),
+            Self::Cancelled => write!(f, "generation cancelled"),
+            Self::Server(message) => write!(f, "ollama error: {message}"),
         }
     }
 }
 
 impl std::error::Error for OllamaStarterError {}
 
+impl OllamaStarterError {
+    /// Whether the failure is transient (connection refused, 429 or 5xx) and worth retrying.
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::HttpClient(err) => err.is_connect(),
+            Self::HttpStatusNotOk(status) => *status == 429 || (500..600).contains(status),
+            _ => false,
+        }
+    }
+}
+
+/// Retry behavior for transient Ollama failures.
+///
+/// Retries never extend past the client request timeout, measured from the
+/// first attempt.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Total attempts including the first one (`1` disables retries).
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each following one.
+    pub base_delay: Duration,
+    /// Upper bound for a single delay.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that never retries.
+    #[must_use]
+    pub const fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        }
+    }
+
+    /// Delay before the given retry (0-based), or `None` if no retry should happen.
+    fn delay_before_retry(&self, retry: u32, elapsed: Duration) -> Option<Duration> {
+        if retry + 1 >= self.max_attempts {
+            return None;
+        }
+        let delay = self
+            .base_delay
+            .saturating_mul(2_u32.saturating_pow(retry))
+            .min(self.max_delay);
+        (elapsed + delay < REQUEST_TIMEOUT).then_some(delay)
+    }
+}
+
+/// Sampling overrides for a generation request.
+///
+/// Unset fields keep Ollama's model defaults, except `num_predict` which
+/// defaults to 512 tokens.
+#[derive(Debug, Clone, Default)]
+pub struct GenerationParams {
+    /// Sampling temperature (`0.0` for deterministic output).
+    pub temperature: Option<f32>,
+    /// Nucleus sampling probability mass.
+    pub top_p: Option<f32>,
+    /// Number of most likely tokens to sample from.
+    pub top_k: Option<u32>,
+    /// Penalty applied to repeated tokens.
+    pub repeat_penalty: Option<f32>,
+    /// Sequences that end generation when produced.
+    pub stop: Vec<String>,
+    /// Maximum number of tokens to generate.
+    pub num_predict: Option<u32>,
+}
+
 #[derive(Serialize)]
-struct GenerateOptions {
+struct GenerateOptions<'a> {
     num_ctx: u32,
     num_predict: u32,
     num_batch: u32,
     num_thread: u32,
     f16_kv: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_gpu: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    main_gpu: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_k: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repeat_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    stop: &'a [String],
 }
 
 #[derive(Serialize)]
@@ -79,23 +219,308 @@ struct GenerateRequest<'a> {
     prompt: &'a str,
     stream: bool,
     keep_alive: &'a str,
-    options: GenerateOptions,
+    options: GenerateOptions<'a>,
+}
+
+/// Author of a chat message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatRole {
+    /// Instructions for the model.
+    System,
+    /// Message from the user.
+    User,
+    /// Reply from the model.
+    Assistant,
+}
+
+/// Message sent to `/api/chat`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    /// Author of the message.
+    pub role: ChatRole,
+    /// Message text.
+    pub content: String,
+}
+
+impl ChatMessage {
+    /// Create a system message.
+    #[must_use]
+    pub fn system(content: impl Into<String>) -> Self {
+        Self {
+            role: ChatRole::System,
+            content: content.into(),
+        }
+    }
+
+    /// Create a user message.
+    #[must_use]
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: ChatRole::User,
+            content: content.into(),
+        }
+    }
+
+    /// Create an assistant message.
+    #[must_use]
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self {
+            role: ChatRole::Assistant,
+            content: content.into(),
+        }
+    }
+}
+
+/// Token counts and timings reported by Ollama for a generation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GenerationStats {
+    /// Tokens in the evaluated prompt.
+    pub prompt_tokens: u32,
+    /// Tokens generated in the completion.
+    pub completion_tokens: u32,
+    /// Time spent generating the completion.
+    pub eval_duration: Duration,
+    /// Total request time, including model load and prompt evaluation.
+    pub total_duration: Duration,
+    /// Completion throughput (`0.0` when Ollama reports no eval duration).
+    pub tokens_per_second: f64,
+}
+
+/// Counters present on the final `/api/generate` response (durations in nanoseconds).
+#[derive(Deserialize, Default)]
+struct GenerateCounters {
+    prompt_eval_count: Option<u32>,
+    eval_count: Option<u32>,
+    eval_duration: Option<u64>,
+    total_duration: Option<u64>,
+}
+
+impl GenerateCounters {
+    fn stats(&self) -> GenerationStats {
+        let completion_tokens = self.eval_count.unwrap_or(0);
+        let eval_duration = Duration::from_nanos(self.eval_duration.unwrap_or(0));
+        let tokens_per_second = if eval_duration.is_zero() {
+            0.0
+        } else {
+            f64::from(completion_tokens) / eval_duration.as_secs_f64()
+        };
+
+        GenerationStats {
+            prompt_tokens: self.prompt_eval_count.unwrap_or(0),
+            completion_tokens,
+            eval_duration,
+            total_duration: Duration::from_nanos(self.total_duration.unwrap_or(0)),
+            tokens_per_second,
+        }
+    }
 }
 
 #[derive(Deserialize)]
 struct GenerateResponse {
     response: Option<String>,
+    #[serde(flatten)]
+    counters: GenerateCounters,
+}
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: &'a [ChatMessage],
+    stream: bool,
+    keep_alive: &'a str,
+    options: GenerateOptions<'a>,
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    message: Option<ChatResponseMessage>,
+    #[serde(flatten)]
+    counters: GenerateCounters,
+}
+
+#[derive(Deserialize)]
+struct ChatResponseMessage {
+    content: String,
+}
+
+/// One NDJSON line of a streamed `/api/generate` or `/api/chat` response.
+#[derive(Deserialize)]
+struct StreamChunk {
+    #[serde(default)]
+    response: String,
+    message: Option<ChatResponseMessage>,
+    #[serde(default)]
+    done: bool,
+    error: Option<String>,
+    #[serde(flatten)]
+    counters: GenerateCounters,
+}
+
+impl StreamChunk {
+    /// Text carried by the chunk, whichever endpoint produced it.
+    fn text(&self) -> &str {
+        self.message
+            .as_ref()
+            .map_or(self.response.as_str(), |m| m.content.as_str())
+    }
+}
+
+/// Model installed on the Ollama server.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OllamaModelInfo {
+    /// Model name including tag (e.g. `ministral-3:8b`).
+    pub name: String,
+    /// Size on disk in bytes.
+    pub size: u64,
+    /// Last modification time (RFC 3339).
+    pub modified_at: String,
+}
+
+#[derive(Deserialize)]
+struct TagsResponse {
+    models: Vec<OllamaModelInfo>,
+}
+
+/// Check whether `model` is in `models`, treating an untagged name as `:latest`.
+fn contains_model(models: &[OllamaModelInfo], model: &str) -> bool {
+    models
+        .iter()
+        .any(|m| m.name == model || m.name.strip_suffix(":latest") == Some(model))
+}
+
+/// Parse one NDJSON line of a streamed response, skipping blank lines.
+///
+/// An `{"error": ...}` line becomes [`OllamaStarterError::Server`].
+fn parse_stream_line(line: &[u8]) -> Result<Option<StreamChunk>, OllamaStarterError> {
+    if line.iter().all(u8::is_ascii_whitespace) {
+        return Ok(None);
+    }
+    let chunk: StreamChunk =
+        serde_json::from_slice(line).map_err(|_| OllamaStarterError::HttpMalformedResponse)?;
+    match chunk.error {
+        Some(message) => Err(OllamaStarterError::Server(message)),
+        None => Ok(Some(chunk)),
+    }
+}
+
+/// Parse every complete line in `buffer`, forwarding text to `on_chunk`.
+///
+/// Returns the stats once the `done` chunk is seen. A trailing partial line
+/// stays buffered, so UTF-8 sequences split across reads are never cut.
+fn drain_stream_lines<F>(
+    buffer: &mut Vec<u8>,
+    on_chunk: &mut F,
+) -> Result<Option<GenerationStats>, OllamaStarterError>
+where
+    F: FnMut(&str),
+{
+    while let Some(end) = buffer.iter().position(|&b| b == b'\n') {
+        let line: Vec<u8> = buffer.drain(..=end).collect();
+        let Some(chunk) = parse_stream_line(&line)? else {
+            continue;
+        };
+        if !chunk.text().is_empty() {
+            on_chunk(chunk.text());
+        }
+        if chunk.done {
+            return Ok(Some(chunk.counters.stats()));
+        }
+    }
+    Ok(None)
+}
+
+/// Read an NDJSON stream until the `done` chunk, forwarding text to `on_chunk`.
+async fn read_stream<F>(
+    mut response: reqwest::Response,
+    cancel: &CancellationToken,
+    mut on_chunk: F,
+) -> Result<GenerationStats, OllamaStarterError>
+where
+    F: FnMut(&str),
+{
+    let mut buffer = Vec::new();
+    loop {
+        let bytes = tokio::select! {
+            biased;
+            () = cancel.cancelled() => return Err(OllamaStarterError::Cancelled),
+            bytes = response.chunk() => bytes?,
+        };
+        let at_eof = bytes.is_none();
+        if let Some(bytes) = bytes {
+            buffer.extend_from_slice(&bytes);
+        } else {
+            // Treat a trailing line without newline as complete.
+            buffer.push(b'\n');
+        }
+
+        if let Some(stats) = drain_stream_lines(&mut buffer, &mut on_chunk)? {
+            return Ok(stats);
+        }
+        if at_eof {
+            return Err(OllamaStarterError::HttpMalformedResponse);
+        }
+    }
+}
+
+/// Limit a request timeout to what is left of `REQUEST_TIMEOUT` after `elapsed`.
+///
+/// Keeps a shorter timeout already set on the request.
+fn cap_timeout(timeout: &mut Option<Duration>, elapsed: Duration) {
+    let remaining = REQUEST_TIMEOUT.saturating_sub(elapsed);
+    *timeout = Some(timeout.map_or(remaining, |t| t.min(remaining)));
+}
+
+/// Turn a non-success status into `HttpStatusNotOk`.
+fn check_status(status: reqwest::StatusCode) -> Result<(), OllamaStarterError> {
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(OllamaStarterError::HttpStatusNotOk(status.as_u16()))
+    }
+}
+
+/// Build generation options for 8K context.
+fn options_8192(
+    num_gpu: Option<u32>,
+    main_gpu: Option<u32>,
+    params: &GenerationParams,
+) -> GenerateOptions<'_> {
+    let num_thread = std::thread::available_parallelism()
+        .map(std::num::NonZeroUsize::get)
+        .map_or(DEFAULT_NUM_THREAD, |v| u32::try_from(v).unwrap_or(u32::MAX));
+
+    GenerateOptions {
+        num_ctx: CONTEXT_LENGTH,
+        num_predict: params.num_predict.unwrap_or(DEFAULT_NUM_PREDICT),
+        num_batch: NUM_BATCH,
+        num_thread,
+        f16_kv: true,
+        num_gpu,
+        main_gpu,
+        temperature: params.temperature,
+        top_p: params.top_p,
+        top_k: params.top_k,
+        repeat_penalty: params.repeat_penalty,
+        stop: &params.stop,
+    }
 }
 
 /// Blocking Ollama client for text generation.
 pub struct OllamaMinistral {
     client: Client,
     base_url: String,
+    num_gpu: Option<u32>,
+    main_gpu: Option<u32>,
+    retry: RetryPolicy,
 }
 
 impl OllamaMinistral {
     /// Create a new client.
     ///
+    /// GPU offload defaults to [`detect_gpu_layers`] and `HALLDYLL_MAIN_GPU`;
+    /// transient failures use [`RetryPolicy::default`].
+    ///
     /// # Errors
     /// Returns an error if the HTTP client cannot be built.
     pub fn new_default() -> Result<Self, OllamaStarterError> {
@@ -107,9 +532,105 @@ impl OllamaMinistral {
         Ok(Self {
             client,
             base_url: get_ollama_url(),
+            num_gpu: detect_gpu_layers(),
+            main_gpu: env_u32(MAIN_GPU_ENV),
+            retry: RetryPolicy::default(),
         })
     }
 
+    /// Set the number of layers offloaded to the GPU (`None` lets Ollama decide).
+    #[must_use]
+    pub fn with_num_gpu(mut self, num_gpu: Option<u32>) -> Self {
+        self.num_gpu = num_gpu;
+        self
+    }
+
+    /// Set the GPU used for small tensors on multi-GPU hosts.
+    #[must_use]
+    pub fn with_main_gpu(mut self, main_gpu: Option<u32>) -> Self {
+        self.main_gpu = main_gpu;
+        self
+    }
+
+    /// Set how transient failures are retried.
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Check whether the Ollama server answers.
+    #[must_use]
+    pub fn is_ready(&self) -> bool {
+        let url = format!("{}/api/version", self.base_url);
+        self.send_with_retry(|| self.client.get(&url).timeout(READY_PROBE_TIMEOUT))
+            .is_ok()
+    }
+
+    /// Wait until the remote Ollama server answers.
+    ///
+    /// The server is managed on `RunPod`, so this only polls it.
+    ///
+    /// # Errors
+    /// Returns `ServerNotReady` if it does not answer within 30 seconds.
+    pub fn ensure_server_running_8192(&self) -> Result<(), OllamaStarterError> {
+        let deadline = Instant::now() + READY_TIMEOUT;
+        while !self.is_ready() {
+            if Instant::now() >= deadline {
+                return Err(OllamaStarterError::ServerNotReady);
+            }
+            std::thread::sleep(READY_POLL_INTERVAL);
+        }
+        Ok(())
+    }
+
+    /// List models installed on the server.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails.
+    pub fn list_models(&self) -> Result<Vec<OllamaModelInfo>, OllamaStarterError> {
+        let url = format!("{}/api/tags", self.base_url);
+        let response = self.send_with_retry(|| self.client.get(&url))?;
+
+        let body: TagsResponse = response.json()?;
+        Ok(body.models)
+    }
+
+    /// Check whether a model is installed.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails.
+    pub fn has_model(&self, name: &str) -> Result<bool, OllamaStarterError> {
+        Ok(contains_model(&self.list_models()?, name))
+    }
+
+    /// Ensure a model is installed before using it.
+    ///
+    /// # Errors
+    /// Returns `ModelNotFound` if the model is missing, or an error if the request fails.
+    pub fn ensure_model_available(&self, name: &str) -> Result<(), OllamaStarterError> {
+        if self.has_model(name)? {
+            Ok(())
+        } else {
+            Err(OllamaStarterError::ModelNotFound(name.to_string()))
+        }
+    }
+
+    /// Load the model into memory with 8K context without generating text.
+    ///
+    /// Ollama loads the model when it receives an empty prompt, so the first
+    /// real generation does not pay the load cost.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails.
+    pub fn preload_ministral_8192(
+        &self,
+        model: &str,
+        keep_alive: &str,
+    ) -> Result<(), OllamaStarterError> {
+        self.generate_8192(model, "", keep_alive).map(|_| ())
+    }
+
     /// Generate text with 8K context.
     ///
     /// # Errors
@@ -120,36 +641,581 @@ impl OllamaMinistral {
         prompt: &str,
         keep_alive: &str,
     ) -> Result<String, OllamaStarterError> {
-        let num_thread = std::thread::available_parallelism()
-            .map(std::num::NonZeroUsize::get)
-            .map_or(DEFAULT_NUM_THREAD, |v| u32::try_from(v).unwrap_or(u32::MAX));
-
-        let options = GenerateOptions {
-            num_ctx: CONTEXT_LENGTH,
-            num_predict: DEFAULT_NUM_PREDICT,
-            num_batch: NUM_BATCH,
-            num_thread,
-            f16_kv: true,
+        self.generate_8192_with_stats(model, prompt, keep_alive)
+            .map(|(text, _)| text)
+    }
+
+    /// Generate text with 8K context and return Ollama's token and timing stats.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails.
+    pub fn generate_8192_with_stats(
+        &self,
+        model: &str,
+        prompt: &str,
+        keep_alive: &str,
+    ) -> Result<(String, GenerationStats), OllamaStarterError> {
+        let params = GenerationParams::default();
+        let request = GenerateRequest {
+            model,
+            prompt,
+            stream: false,
+            keep_alive,
+            options: options_8192(self.num_gpu, self.main_gpu, &params),
         };
 
+        self.post_generate(&request)
+    }
+
+    /// Generate text with 8K context and custom sampling parameters.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails.
+    pub fn generate_with_params(
+        &self,
+        model: &str,
+        prompt: &str,
+        keep_alive: &str,
+        params: &GenerationParams,
+    ) -> Result<String, OllamaStarterError> {
         let request = GenerateRequest {
             model,
             prompt,
             stream: false,
             keep_alive,
-            options,
+            options: options_8192(self.num_gpu, self.main_gpu, params),
+        };
+
+        self.post_generate(&request).map(|(text, _)| text)
+    }
+
+    /// Send a non-streaming request to `/api/generate`.
+    fn post_generate(
+        &self,
+        request: &GenerateRequest<'_>,
+    ) -> Result<(String, GenerationStats), OllamaStarterError> {
+        let url = format!("{}/api/generate", self.base_url);
+        let response = self.send_with_retry(|| self.client.post(&url).json(request))?;
+
+        let body: GenerateResponse = response.json()?;
+        let text = body
+            .response
+            .ok_or(OllamaStarterError::HttpMalformedResponse)?;
+        Ok((text, body.counters.stats()))
+    }
+
+    /// Send a conversation to `/api/chat` with 8K context and return the assistant reply.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails.
+    pub fn chat(
+        &self,
+        model: &str,
+        messages: &[ChatMessage],
+        keep_alive: &str,
+    ) -> Result<String, OllamaStarterError> {
+        self.chat_with_stats(model, messages, keep_alive)
+            .map(|(text, _)| text)
+    }
+
+    /// Send a conversation to `/api/chat` and return the reply with Ollama's token and timing stats.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails.
+    pub fn chat_with_stats(
+        &self,
+        model: &str,
+        messages: &[ChatMessage],
+        keep_alive: &str,
+    ) -> Result<(String, GenerationStats), OllamaStarterError> {
+        let params = GenerationParams::default();
+        let request = ChatRequest {
+            model,
+            messages,
+            stream: false,
+            keep_alive,
+            options: options_8192(self.num_gpu, self.main_gpu, &params),
+        };
+
+        let url = format!("{}/api/chat", self.base_url);
+        let response = self.send_with_retry(|| self.client.post(&url).json(&request))?;
+
+        let body: ChatResponse = response.json()?;
+        let text = body
+            .message
+            .map(|m| m.content)
+            .ok_or(OllamaStarterError::HttpMalformedResponse)?;
+        Ok((text, body.counters.stats()))
+    }
+
+    /// Generate text with 8K context, streaming the completion as it is produced.
+    ///
+    /// `on_chunk` receives each non-empty piece of text in order. The NDJSON body
+    /// is buffered line by line, so UTF-8 sequences split across reads are never cut.
+    /// Returns the stats reported with the final chunk.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails or the stream ends before `done`.
+    pub fn generate_stream<F>(
+        &self,
+        model: &str,
+        prompt: &str,
+        keep_alive: &str,
+        mut on_chunk: F,
+    ) -> Result<GenerationStats, OllamaStarterError>
+    where
+        F: FnMut(&str),
+    {
+        let params = GenerationParams::default();
+        let request = GenerateRequest {
+            model,
+            prompt,
+            stream: true,
+            keep_alive,
+            options: options_8192(self.num_gpu, self.main_gpu, &params),
         };
 
         let url = format!("{}/api/generate", self.base_url);
-        let response = self.client.post(&url).json(&request).send()?;
+        let response = self.send_with_retry(|| self.client.post(&url).json(&request))?;
 
-        let status = response.status();
-        if !status.is_success() {
-            return Err(OllamaStarterError::HttpStatusNotOk(status.as_u16()));
+        let mut reader = BufReader::new(response);
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            let read = reader
+                .read_until(b'\n', &mut line)
+                .map_err(OllamaStarterError::StreamRead)?;
+            if read == 0 {
+                return Err(OllamaStarterError::HttpMalformedResponse);
+            }
+
+            let Some(chunk) = parse_stream_line(&line)? else {
+                continue;
+            };
+            if !chunk.text().is_empty() {
+                on_chunk(chunk.text());
+            }
+            if chunk.done {
+                return Ok(chunk.counters.stats());
+            }
         }
+    }
 
-        let body: GenerateResponse = response.json()?;
-        body.response.ok_or(OllamaStarterError::HttpMalformedResponse)
+    /// Send a request, retrying transient failures according to the retry policy.
+    ///
+    /// Each attempt only gets what is left of `REQUEST_TIMEOUT`.
+    fn send_with_retry<F>(&self, mut build: F) -> Result<Response, OllamaStarterError>
+    where
+        F: FnMut() -> RequestBuilder,
+    {
+        let started = Instant::now();
+        let mut retry = 0;
+        loop {
+            let result = build()
+                .build()
+                .and_then(|mut request| {
+                    cap_timeout(request.timeout_mut(), started.elapsed());
+                    self.client.execute(request)
+                })
+                .map_err(OllamaStarterError::from)
+                .and_then(|r| check_status(r.status()).map(|()| r));
+            match result {
+                Err(err) if err.is_transient() => {
+                    let Some(delay) = self.retry.delay_before_retry(retry, started.elapsed())
+                    else {
+                        return Err(err);
+                    };
+                    tracing::warn!("Ollama request failed ({err}), retrying in {delay:?}");
+                    std::thread::sleep(delay);
+                    retry += 1;
+                }
+                other => return other,
+            }
+        }
+    }
+}
+
+/// Async Ollama client for text generation.
+///
+/// Mirrors [`OllamaMinistral`] for callers already running on Tokio.
+pub struct AsyncOllamaMinistral {
+    client: reqwest::Client,
+    base_url: String,
+    num_gpu: Option<u32>,
+    main_gpu: Option<u32>,
+    retry: RetryPolicy,
+}
+
+impl AsyncOllamaMinistral {
+    /// Create a new client.
+    ///
+    /// GPU offload defaults to [`detect_gpu_layers`] and `HALLDYLL_MAIN_GPU`;
+    /// transient failures use [`RetryPolicy::default`].
+    ///
+    /// # Errors
+    /// Returns an error if the HTTP client cannot be built.
+    pub fn new_default() -> Result<Self, OllamaStarterError> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+
+        Ok(Self {
+            client,
+            base_url: get_ollama_url(),
+            num_gpu: detect_gpu_layers(),
+            main_gpu: env_u32(MAIN_GPU_ENV),
+            retry: RetryPolicy::default(),
+        })
+    }
+
+    /// Set the number of layers offloaded to the GPU (`None` lets Ollama decide).
+    #[must_use]
+    pub fn with_num_gpu(mut self, num_gpu: Option<u32>) -> Self {
+        self.num_gpu = num_gpu;
+        self
+    }
+
+    /// Set the GPU used for small tensors on multi-GPU hosts.
+    #[must_use]
+    pub fn with_main_gpu(mut self, main_gpu: Option<u32>) -> Self {
+        self.main_gpu = main_gpu;
+        self
+    }
+
+    /// Set how transient failures are retried.
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Check whether the Ollama server answers.
+    pub async fn is_ready(&self) -> bool {
+        let url = format!("{}/api/version", self.base_url);
+        self.send_with_retry(|| self.client.get(&url).timeout(READY_PROBE_TIMEOUT))
+            .await
+            .is_ok()
+    }
+
+    /// Wait until the remote Ollama server answers.
+    ///
+    /// The server is managed on `RunPod`, so this only polls it.
+    ///
+    /// # Errors
+    /// Returns `ServerNotReady` if it does not answer within 30 seconds.
+    pub async fn ensure_server_running_8192(&self) -> Result<(), OllamaStarterError> {
+        let deadline = tokio::time::Instant::now() + READY_TIMEOUT;
+        while !self.is_ready().await {
+            if tokio::time::Instant::now() >= deadline {
+                return Err(OllamaStarterError::ServerNotReady);
+            }
+            tokio::time::sleep(READY_POLL_INTERVAL).await;
+        }
+        Ok(())
+    }
+
+    /// List models installed on the server.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails.
+    pub async fn list_models(&self) -> Result<Vec<OllamaModelInfo>, OllamaStarterError> {
+        let url = format!("{}/api/tags", self.base_url);
+        let response = self.send_with_retry(|| self.client.get(&url)).await?;
+
+        let body: TagsResponse = response.json().await?;
+        Ok(body.models)
+    }
+
+    /// Check whether a model is installed.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails.
+    pub async fn has_model(&self, name: &str) -> Result<bool, OllamaStarterError> {
+        Ok(contains_model(&self.list_models().await?, name))
+    }
+
+    /// Ensure a model is installed before using it.
+    ///
+    /// # Errors
+    /// Returns `ModelNotFound` if the model is missing, or an error if the request fails.
+    pub async fn ensure_model_available(&self, name: &str) -> Result<(), OllamaStarterError> {
+        if self.has_model(name).await? {
+            Ok(())
+        } else {
+            Err(OllamaStarterError::ModelNotFound(name.to_string()))
+        }
+    }
+
+    /// Load the model into memory with 8K context without generating text.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails.
+    pub async fn preload_ministral_8192(
+        &self,
+        model: &str,
+        keep_alive: &str,
+    ) -> Result<(), OllamaStarterError> {
+        self.generate_8192(model, "", keep_alive).await.map(|_| ())
+    }
+
+    /// Generate text with 8K context.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails.
+    pub async fn generate_8192(
+        &self,
+        model: &str,
+        prompt: &str,
+        keep_alive: &str,
+    ) -> Result<String, OllamaStarterError> {
+        self.generate_8192_with_stats(model, prompt, keep_alive)
+            .await
+            .map(|(text, _)| text)
+    }
+
+    /// Generate text with 8K context and return Ollama's token and timing stats.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails.
+    pub async fn generate_8192_with_stats(
+        &self,
+        model: &str,
+        prompt: &str,
+        keep_alive: &str,
+    ) -> Result<(String, GenerationStats), OllamaStarterError> {
+        let params = GenerationParams::default();
+        let request = GenerateRequest {
+            model,
+            prompt,
+            stream: false,
+            keep_alive,
+            options: options_8192(self.num_gpu, self.main_gpu, &params),
+        };
+
+        self.post_generate(&request).await
+    }
+
+    /// Generate text with 8K context and custom sampling parameters.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails.
+    pub async fn generate_with_params(
+        &self,
+        model: &str,
+        prompt: &str,
+        keep_alive: &str,
+        params: &GenerationParams,
+    ) -> Result<String, OllamaStarterError> {
+        let request = GenerateRequest {
+            model,
+            prompt,
+            stream: false,
+            keep_alive,
+            options: options_8192(self.num_gpu, self.main_gpu, params),
+        };
+
+        self.post_generate(&request).await.map(|(text, _)| text)
+    }
+
+    /// Generate text with 8K context, aborting when `cancel` is triggered.
+    ///
+    /// Cancelling drops the HTTP request, which makes Ollama stop generating.
+    ///
+    /// # Errors
+    /// Returns `Cancelled` if cancelled first, or an error if the request fails.
+    pub async fn generate_8192_cancellable(
+        &self,
+        model: &str,
+        prompt: &str,
+        keep_alive: &str,
+        cancel: &CancellationToken,
+    ) -> Result<String, OllamaStarterError> {
+        tokio::select! {
+            biased;
+            () = cancel.cancelled() => Err(OllamaStarterError::Cancelled),
+            result = self.generate_8192(model, prompt, keep_alive) => result,
+        }
+    }
+
+    /// Generate text with 8K context, streaming the completion until done or cancelled.
+    ///
+    /// `on_chunk` receives each non-empty piece of text in order. Network reads are
+    /// split on complete NDJSON lines, so UTF-8 sequences are never cut. Cancelling
+    /// closes the connection mid-stream. Returns the stats reported with the final chunk.
+    ///
+    /// # Errors
+    /// Returns `Cancelled` if cancelled, or an error if the request fails or the
+    /// stream ends before `done`.
+    pub async fn generate_stream<F>(
+        &self,
+        model: &str,
+        prompt: &str,
+        keep_alive: &str,
+        cancel: &CancellationToken,
+        on_chunk: F,
+    ) -> Result<GenerationStats, OllamaStarterError>
+    where
+        F: FnMut(&str),
+    {
+        let params = GenerationParams::default();
+        let request = GenerateRequest {
+            model,
+            prompt,
+            stream: true,
+            keep_alive,
+            options: options_8192(self.num_gpu, self.main_gpu, &params),
+        };
+
+        let url = format!("{}/api/generate", self.base_url);
+        let send = self.send_with_retry(|| self.client.post(&url).json(&request));
+        let response = tokio::select! {
+            biased;
+            () = cancel.cancelled() => return Err(OllamaStarterError::Cancelled),
+            response = send => response?,
+        };
+
+        read_stream(response, cancel, on_chunk).await
+    }
+
+    /// Stream a conversation through `/api/chat` with 8K context until done or cancelled.
+    ///
+    /// `on_chunk` receives each non-empty piece of the assistant reply in order.
+    /// Returns the stats reported with the final chunk.
+    ///
+    /// # Errors
+    /// Returns `Cancelled` if cancelled, or an error if the request fails or the
+    /// stream ends before `done`.
+    pub async fn chat_stream<F>(
+        &self,
+        model: &str,
+        messages: &[ChatMessage],
+        keep_alive: &str,
+        cancel: &CancellationToken,
+        on_chunk: F,
+    ) -> Result<GenerationStats, OllamaStarterError>
+    where
+        F: FnMut(&str),
+    {
+        let params = GenerationParams::default();
+        let request = ChatRequest {
+            model,
+            messages,
+            stream: true,
+            keep_alive,
+            options: options_8192(self.num_gpu, self.main_gpu, &params),
+        };
+
+        let url = format!("{}/api/chat", self.base_url);
+        let send = self.send_with_retry(|| self.client.post(&url).json(&request));
+        let response = tokio::select! {
+            biased;
+            () = cancel.cancelled() => return Err(OllamaStarterError::Cancelled),
+            response = send => response?,
+        };
+
+        read_stream(response, cancel, on_chunk).await
+    }
+
+    /// Send a non-streaming request to `/api/generate`.
+    async fn post_generate(
+        &self,
+        request: &GenerateRequest<'_>,
+    ) -> Result<(String, GenerationStats), OllamaStarterError> {
+        let url = format!("{}/api/generate", self.base_url);
+        let response = self
+            .send_with_retry(|| self.client.post(&url).json(request))
+            .await?;
+
+        let body: GenerateResponse = response.json().await?;
+        let text = body
+            .response
+            .ok_or(OllamaStarterError::HttpMalformedResponse)?;
+        Ok((text, body.counters.stats()))
+    }
+
+    /// Send a conversation to `/api/chat` with 8K context and return the assistant reply.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails.
+    pub async fn chat(
+        &self,
+        model: &str,
+        messages: &[ChatMessage],
+        keep_alive: &str,
+    ) -> Result<String, OllamaStarterError> {
+        self.chat_with_stats(model, messages, keep_alive)
+            .await
+            .map(|(text, _)| text)
+    }
+
+    /// Send a conversation to `/api/chat` and return the reply with Ollama's token and timing stats.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails.
+    pub async fn chat_with_stats(
+        &self,
+        model: &str,
+        messages: &[ChatMessage],
+        keep_alive: &str,
+    ) -> Result<(String, GenerationStats), OllamaStarterError> {
+        let params = GenerationParams::default();
+        let request = ChatRequest {
+            model,
+            messages,
+            stream: false,
+            keep_alive,
+            options: options_8192(self.num_gpu, self.main_gpu, &params),
+        };
+
+        let url = format!("{}/api/chat", self.base_url);
+        let response = self
+            .send_with_retry(|| self.client.post(&url).json(&request))
+            .await?;
+
+        let body: ChatResponse = response.json().await?;
+        let text = body
+            .message
+            .map(|m| m.content)
+            .ok_or(OllamaStarterError::HttpMalformedResponse)?;
+        Ok((text, body.counters.stats()))
+    }
+
+    /// Send a request, retrying transient failures according to the retry policy.
+    ///
+    /// Each attempt only gets what is left of `REQUEST_TIMEOUT`.
+    async fn send_with_retry<F>(
+        &self,
+        mut build: F,
+    ) -> Result<reqwest::Response, OllamaStarterError>
+    where
+        F: FnMut() -> reqwest::RequestBuilder,
+    {
+        let started = tokio::time::Instant::now();
+        let mut retry = 0;
+        loop {
+            let result = match build().build() {
+                Ok(mut request) => {
+                    cap_timeout(request.timeout_mut(), started.elapsed());
+                    self.client.execute(request).await
+                }
+                Err(err) => Err(err),
+            }
+            .map_err(OllamaStarterError::from)
+            .and_then(|r| check_status(r.status()).map(|()| r));
+            match result {
+                Err(err) if err.is_transient() => {
+                    let Some(delay) = self.retry.delay_before_retry(retry, started.elapsed())
+                    else {
+                        return Err(err);
+                    };
+                    tracing::warn!("Ollama request failed ({err}), retrying in {delay:?}");
+                    tokio::time::sleep(delay).await;
+                    retry += 1;
+                }
+                other => return other,
+            }
+        }
     }
 }
 
@@ -161,3 +1227,49 @@ pub fn ensure_ollama_and_preload_ministral() -> Result<(), OllamaStarterError> {
     // In cloud mode, Ollama is already running on RunPod
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drain_stream_lines_waits_for_utf8_split_across_reads() {
+        let stream = "{\"response\":\"é\",\"done\":false}\n{\"response\":\"\",\"done\":true,\"eval_count\":1}\n";
+        let split = stream.find('é').map_or(0, |i| i + 1);
+        let mut buffer = stream.as_bytes()[..split].to_vec();
+        let mut text = String::new();
+        let mut on_chunk = |s: &str| text.push_str(s);
+
+        assert!(matches!(
+            drain_stream_lines(&mut buffer, &mut on_chunk),
+            Ok(None)
+        ));
+
+        buffer.extend_from_slice(&stream.as_bytes()[split..]);
+        let stats = drain_stream_lines(&mut buffer, &mut on_chunk);
+        assert!(matches!(stats, Ok(Some(ref s)) if s.completion_tokens == 1));
+        assert_eq!(text, "é");
+    }
+
+    #[test]
+    fn parse_stream_line_surfaces_ollama_errors() {
+        let result = parse_stream_line(b"{\"error\":\"model crashed\"}\n");
+        assert!(matches!(result, Err(OllamaStarterError::Server(ref m)) if m == "model crashed"));
+    }
+
+    #[test]
+    fn cap_timeout_limits_attempts_to_the_remaining_budget() {
+        let mut timeout = None;
+        cap_timeout(&mut timeout, Duration::from_secs(90));
+        assert_eq!(timeout, Some(Duration::from_secs(30)));
+
+        let mut timeout = Some(READY_PROBE_TIMEOUT);
+        cap_timeout(&mut timeout, Duration::from_secs(1));
+        assert_eq!(timeout, Some(READY_PROBE_TIMEOUT));
+    }
+
+    #[test]
+    fn parse_stream_line_skips_blank_lines() {
+        assert!(matches!(parse_stream_line(b" \r\n"), Ok(None)));
+    }
+}
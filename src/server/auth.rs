@@ -0,0 +1,123 @@
+//! Optional bearer token authentication for the API routes.
+
+use std::fmt;
+
+use axum::extract::{Request, State};
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+/// Environment variable holding the API key.
+const API_KEY_ENV: &str = "HALLDYLL_API_KEY";
+
+/// Bearer token authentication settings.
+///
+/// When no token is configured, every request is allowed.
+#[derive(Clone, Default)]
+pub struct AuthConfig {
+    token: Option<String>,
+}
+
+impl AuthConfig {
+    /// Require `Authorization: Bearer <token>` on API routes.
+    #[must_use]
+    pub fn with_token(token: impl Into<String>) -> Self {
+        Self {
+            token: Some(token.into()),
+        }
+    }
+
+    /// Read the token from `HALLDYLL_API_KEY`; unset or empty disables auth.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let token = std::env::var(API_KEY_ENV)
+            .ok()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty());
+        Self { token }
+    }
+
+    /// Whether a token is required.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.token.is_some()
+    }
+
+    /// Check an `Authorization` header value against the configured token.
+    fn authorizes(&self, header: Option<&HeaderValue>) -> bool {
+        let Some(expected) = &self.token else {
+            return true;
+        };
+        header
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split_once(' '))
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+            .is_some_and(|(_, given)| {
+                constant_time_eq(given.trim().as_bytes(), expected.as_bytes())
+            })
+    }
+}
+
+impl fmt::Debug for AuthConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthConfig")
+            .field("enabled", &self.is_enabled())
+            .finish()
+    }
+}
+
+/// Compare two byte strings without short-circuiting on the first mismatch.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Reject `/api/*` requests without a valid bearer token.
+///
+/// The health check and static frontend stay public.
+pub(crate) async fn require_bearer(
+    State(auth): State<AuthConfig>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !request.uri().path().starts_with("/api/")
+        || auth.authorizes(request.headers().get(header::AUTHORIZATION))
+    {
+        return next.run(request).await;
+    }
+
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, "Bearer")],
+        "Missing or invalid API key",
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn authorizes_everything_without_a_token() {
+        let auth = AuthConfig::default();
+        assert!(auth.authorizes(None));
+        assert!(auth.authorizes(Some(&HeaderValue::from_static("Bearer anything"))));
+    }
+
+    #[test]
+    fn rejects_missing_or_wrong_token() {
+        let auth = AuthConfig::with_token("s3cret");
+        assert!(!auth.authorizes(None));
+        assert!(!auth.authorizes(Some(&HeaderValue::from_static("Bearer wrong"))));
+        assert!(!auth.authorizes(Some(&HeaderValue::from_static("Basic s3cret"))));
+        assert!(!auth.authorizes(Some(&HeaderValue::from_static("Bearer s3cret2"))));
+    }
+
+    #[test]
+    fn accepts_correct_token_with_any_scheme_case() {
+        let auth = AuthConfig::with_token("s3cret");
+        assert!(auth.authorizes(Some(&HeaderValue::from_static("Bearer s3cret"))));
+        assert!(auth.authorizes(Some(&HeaderValue::from_static("bearer s3cret"))));
+        assert!(auth.authorizes(Some(&HeaderValue::from_static("BEARER s3cret"))));
+    }
+}
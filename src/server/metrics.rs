@@ -0,0 +1,75 @@
+//! Prometheus metrics for the API server (`metrics` feature).
+
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use axum::http::header;
+use axum::response::IntoResponse;
+use metrics::{counter, describe_counter, describe_histogram, histogram};
+use metrics_exporter_prometheus::{BuildError, Matcher, PrometheusBuilder, PrometheusHandle};
+
+use crate::llm::ollama_starter_ministral::{GenerationStats, OllamaStarterError};
+
+/// Chat requests by endpoint and outcome.
+const CHAT_REQUESTS: &str = "halldyll_chat_requests_total";
+
+/// Time spent waiting on the LLM for successful chats.
+const LLM_LATENCY: &str = "halldyll_llm_latency_seconds";
+
+/// Completion tokens produced by the LLM.
+const TOKENS_GENERATED: &str = "halldyll_tokens_generated_total";
+
+/// Histogram buckets for LLM latency, in seconds.
+const LATENCY_BUCKETS: &[f64] = &[0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0];
+
+static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Install the global Prometheus recorder. Later calls do nothing.
+///
+/// # Errors
+/// Returns an error if another recorder is already installed.
+pub fn install() -> Result<(), BuildError> {
+    if HANDLE.get().is_some() {
+        return Ok(());
+    }
+
+    let handle = PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Full(LLM_LATENCY.to_string()), LATENCY_BUCKETS)?
+        .install_recorder()?;
+    let _ = HANDLE.set(handle);
+
+    describe_counter!(CHAT_REQUESTS, "Chat requests by endpoint and outcome");
+    describe_histogram!(
+        LLM_LATENCY,
+        "Time spent waiting on the LLM for successful chats"
+    );
+    describe_counter!(TOKENS_GENERATED, "Completion tokens produced by the LLM");
+    Ok(())
+}
+
+/// Record a finished chat request started at `started`.
+pub(crate) fn record_chat(
+    endpoint: &'static str,
+    started: Instant,
+    result: Result<&GenerationStats, &OllamaStarterError>,
+) {
+    let outcome = match result {
+        Ok(stats) => {
+            histogram!(LLM_LATENCY, "endpoint" => endpoint).record(started.elapsed().as_secs_f64());
+            counter!(TOKENS_GENERATED).increment(u64::from(stats.completion_tokens));
+            "ok"
+        }
+        Err(OllamaStarterError::Cancelled) => "cancelled",
+        Err(_) => "error",
+    };
+    counter!(CHAT_REQUESTS, "endpoint" => endpoint, "outcome" => outcome).increment(1);
+}
+
+/// Render all metrics in the Prometheus text format.
+pub(crate) async fn metrics_handler() -> impl IntoResponse {
+    let body = HANDLE.get().map_or_else(String::new, |handle| {
+        handle.run_upkeep();
+        handle.render()
+    });
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body)
+}
@@ -5,61 +5,235 @@
 //! - Web search
 //! - Memory operations
 
+pub mod auth;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod rate_limit;
 pub mod routes;
 pub mod state;
 
+pub use auth::AuthConfig;
+pub use rate_limit::RateLimiter;
 pub use routes::create_router;
 pub use state::AppState;
 
 use std::future::Future;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Instant;
 
-use axum::Router;
+use axum::{Router, middleware};
+use tokio_util::sync::CancellationToken;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 
 /// Default server port.
 pub const DEFAULT_PORT: u16 = 3000;
 
+/// How long Ollama keeps the model loaded after a request.
+pub(crate) const MODEL_KEEP_ALIVE: &str = "5m";
+
+/// Server startup configuration.
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Port to listen on.
+    pub port: u16,
+    /// Load the model before accepting connections.
+    pub warm_up_on_start: bool,
+    /// Bearer token required on `/api/*` routes.
+    pub auth: AuthConfig,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            port: DEFAULT_PORT,
+            warm_up_on_start: false,
+            auth: AuthConfig::default(),
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Read configuration from `HALLDYLL_PORT`, `HALLDYLL_WARM_UP` and
+    /// `HALLDYLL_API_KEY`.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let port = std::env::var("HALLDYLL_PORT")
+            .ok()
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(defaults.port);
+
+        let warm_up_on_start =
+            std::env::var("HALLDYLL_WARM_UP").map_or(defaults.warm_up_on_start, |v| {
+                let v = v.trim();
+                ["1", "true", "yes"]
+                    .iter()
+                    .any(|on| v.eq_ignore_ascii_case(on))
+            });
+
+        Self {
+            port,
+            warm_up_on_start,
+            auth: AuthConfig::from_env(),
+        }
+    }
+}
+
 /// Start the HTTP server.
 ///
 /// # Errors
 /// Returns an error if the server fails to start.
-pub async fn run_server(state: Arc<AppState>, port: u16) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    run_server_with_shutdown(state, port, std::future::pending()).await
+pub async fn run_server(
+    state: Arc<AppState>,
+    config: ServerConfig,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    run_server_with_shutdown(state, config, std::future::pending()).await
 }
 
 /// Start the HTTP server with graceful shutdown support.
 ///
+/// The model is preloaded first when `config.warm_up_on_start` is set, and
+/// `config.auth` guards the `/api/*` routes when a token is configured.
 /// The server will stop accepting new connections when `shutdown_signal` completes.
 ///
 /// # Errors
 /// Returns an error if the server fails to start.
 pub async fn run_server_with_shutdown<F>(
     state: Arc<AppState>,
-    port: u16,
+    config: ServerConfig,
     shutdown_signal: F,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
 where
     F: Future<Output = ()> + Send + 'static,
 {
+    if config.warm_up_on_start {
+        warm_up(&state).await;
+    }
+
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
         .allow_headers(Any);
 
-    let app: Router = create_router(state)
+    if !config.auth.is_enabled() {
+        tracing::warn!("No API key configured, the API is open to anyone");
+    }
+
+    #[cfg(feature = "metrics")]
+    if let Err(e) = metrics::install() {
+        tracing::warn!("Failed to install metrics recorder: {e}");
+    }
+
+    // Stops the sweeper however this function returns.
+    let stop_background = CancellationToken::new();
+    let _stop_background = stop_background.clone().drop_guard();
+    if state.rate_limiter.is_some() {
+        spawn_rate_limit_sweeper(Arc::clone(&state), stop_background);
+    }
+
+    let app: Router = create_router(Arc::clone(&state))
+        .layer(middleware::from_fn_with_state(
+            config.auth,
+            auth::require_bearer,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state,
+            rate_limit::limit_requests,
+        ))
         .layer(cors)
         .layer(TraceLayer::new_for_http());
 
-    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
     tracing::info!("Halldyll Agent server listening on http://{}", addr);
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal)
-        .await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal)
+    .await?;
 
     Ok(())
 }
+
+/// Complete when the process is asked to stop.
+///
+/// Listens for Ctrl+C everywhere and also for SIGTERM on Unix, which is what
+/// `kill`/`pkill` and container runtimes send. Windows only delivers Ctrl+C.
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl+C: {e}");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {e}");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {}
+        () = terminate => {}
+    }
+
+    tracing::info!("Shutdown signal received, draining connections");
+}
+
+/// Periodically drop idle rate limiter buckets until `stop` is cancelled.
+fn spawn_rate_limit_sweeper(state: Arc<AppState>, stop: CancellationToken) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(rate_limit::SWEEP_INTERVAL);
+        loop {
+            tokio::select! {
+                () = stop.cancelled() => break,
+                _ = interval.tick() => {
+                    if let Some(limiter) = &state.rate_limiter {
+                        limiter.sweep();
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Preload the model so the first request does not pay the load cost.
+///
+/// Failures are logged but do not prevent the server from starting.
+async fn warm_up(state: &Arc<AppState>) {
+    tracing::info!("Warming up model {}...", state.model_name);
+    let started = Instant::now();
+
+    let result = async {
+        state
+            .ollama
+            .ensure_model_available(&state.model_name)
+            .await?;
+        state
+            .ollama
+            .preload_ministral_8192(&state.model_name, MODEL_KEEP_ALIVE)
+            .await
+    }
+    .await;
+
+    match result {
+        Ok(()) => tracing::info!("Model warmed up in {:.1?}", started.elapsed()),
+        Err(e) => tracing::warn!("Model warm-up failed after {:.1?}: {e}", started.elapsed()),
+    }
+}
@@ -0,0 +1,212 @@
+//! Per-client rate limiting for the API routes.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use super::state::AppState;
+
+/// Time for an empty bucket to refill completely.
+const REFILL_PERIOD: Duration = Duration::from_mins(1);
+
+/// Header listing the client and proxy addresses a request went through.
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// How often idle buckets are dropped.
+pub(crate) const SWEEP_INTERVAL: Duration = Duration::from_mins(5);
+
+/// Token bucket for one client.
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token bucket rate limiter keyed by client IP.
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: f64,
+    per_second: f64,
+    trusted_proxy_hops: usize,
+    buckets: Mutex<HashMap<IpAddr, TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Allow `requests_per_minute` requests per client, with bursts up to the same amount.
+    #[must_use]
+    pub fn new(requests_per_minute: u32) -> Self {
+        let capacity = f64::from(requests_per_minute.max(1));
+        Self {
+            capacity,
+            per_second: capacity / REFILL_PERIOD.as_secs_f64(),
+            trusted_proxy_hops: 0,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Trust the last `hops` entries of `X-Forwarded-For`.
+    ///
+    /// Use `1` behind a single reverse proxy such as the `RunPod` HTTP proxy,
+    /// otherwise every client shares the proxy's address. `0` (the default)
+    /// keys on the TCP peer.
+    #[must_use]
+    pub const fn with_trusted_proxy_hops(mut self, hops: usize) -> Self {
+        self.trusted_proxy_hops = hops;
+        self
+    }
+
+    /// Client address to key on, given the `X-Forwarded-For` values and the TCP peer.
+    ///
+    /// Falls back to the peer when the header has fewer entries than trusted hops.
+    fn client_ip(&self, forwarded_for: &str, peer: IpAddr) -> IpAddr {
+        if self.trusted_proxy_hops == 0 {
+            return peer;
+        }
+        let hops: Vec<&str> = forwarded_for
+            .split(',')
+            .map(str::trim)
+            .filter(|hop| !hop.is_empty())
+            .collect();
+        hops.len()
+            .checked_sub(self.trusted_proxy_hops)
+            .and_then(|i| hops[i].parse().ok())
+            .unwrap_or(peer)
+    }
+
+    /// Take a token for `client`, or return how long to wait for the next one.
+    ///
+    /// # Errors
+    /// Returns the wait time when the client has no tokens left.
+    pub fn check(&self, client: IpAddr) -> Result<(), Duration> {
+        self.check_at(client, Instant::now())
+    }
+
+    /// [`Self::check`] at a given instant.
+    fn check_at(&self, client: IpAddr, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        let bucket = buckets.entry(client).or_insert(TokenBucket {
+            tokens: self.capacity,
+            last_refill: now,
+        });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = elapsed
+            .mul_add(self.per_second, bucket.tokens)
+            .min(self.capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / self.per_second,
+            ))
+        }
+    }
+
+    /// Drop buckets that have been idle long enough to be full again.
+    pub fn sweep(&self) {
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        buckets.retain(|_, bucket| bucket.last_refill.elapsed() < REFILL_PERIOD);
+    }
+}
+
+/// Answer `/api/*` requests with 429 once the client runs out of tokens.
+///
+/// The health and readiness probes and the static frontend are not limited.
+pub(crate) async fn limit_requests(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(limiter) = &state.rate_limiter else {
+        return next.run(request).await;
+    };
+    let Some(ConnectInfo(peer)) = request.extensions().get::<ConnectInfo<SocketAddr>>() else {
+        return next.run(request).await;
+    };
+
+    let forwarded_for = request
+        .headers()
+        .get_all(X_FORWARDED_FOR)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .collect::<Vec<_>>()
+        .join(",");
+    let client = limiter.client_ip(&forwarded_for, peer.ip());
+
+    if request.uri().path().starts_with("/api/")
+        && let Err(wait) = limiter.check(client)
+    {
+        let retry_after = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+        tracing::warn!("Rate limit exceeded for {client}");
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, retry_after.to_string())],
+            "Too many requests",
+        )
+            .into_response();
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    const CLIENT: IpAddr = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 7));
+    const PROXY: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+
+    #[test]
+    fn check_allows_a_burst_then_reports_the_wait() {
+        let limiter = RateLimiter::new(60);
+        let start = Instant::now();
+        for _ in 0..60 {
+            assert!(limiter.check_at(CLIENT, start).is_ok());
+        }
+
+        let Err(wait) = limiter.check_at(CLIENT, start) else {
+            panic!("the 61st request in a burst was allowed");
+        };
+        assert!(wait.abs_diff(Duration::from_secs(1)) < Duration::from_millis(1));
+        assert!(limiter.check_at(PROXY, start).is_ok());
+    }
+
+    #[test]
+    fn check_refills_over_time() {
+        let limiter = RateLimiter::new(60);
+        let start = Instant::now();
+        for _ in 0..60 {
+            assert!(limiter.check_at(CLIENT, start).is_ok());
+        }
+
+        let later = start + Duration::from_secs(2);
+        assert!(limiter.check_at(CLIENT, later).is_ok());
+        assert!(limiter.check_at(CLIENT, later).is_ok());
+        assert!(limiter.check_at(CLIENT, later).is_err());
+    }
+
+    #[test]
+    fn client_ip_uses_trusted_forwarded_hops() {
+        let direct = RateLimiter::new(1);
+        assert_eq!(direct.client_ip("203.0.113.7", PROXY), PROXY);
+
+        let proxied = RateLimiter::new(1).with_trusted_proxy_hops(1);
+        assert_eq!(
+            proxied.client_ip("198.51.100.1, 203.0.113.7", PROXY),
+            CLIENT
+        );
+        assert_eq!(proxied.client_ip("", PROXY), PROXY);
+        assert_eq!(proxied.client_ip("not-an-ip", PROXY), PROXY);
+    }
+}
@@ -1,22 +1,36 @@
 //! HTTP route handlers for the Halldyll agent API.
 
+use std::convert::Infallible;
 use std::sync::Arc;
 
 use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::routing::{get, post};
 use axum::{Json, Router};
+use futures_util::Stream;
 use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 use tower_http::services::ServeDir;
 
+use super::MODEL_KEEP_ALIVE;
 use super::state::AppState;
+use crate::llm::ollama_starter_ministral::{ChatMessage, OllamaStarterError};
 
 /// Create the API router with all routes.
 pub fn create_router(state: Arc<AppState>) -> Router {
-    Router::new()
+    let router = Router::new()
         .route("/health", get(health_check))
+        .route("/ready", get(readiness_check))
         .route("/api/chat", post(chat_completion))
+        .route("/api/chat/stream", post(chat_completion_stream));
+
+    #[cfg(feature = "metrics")]
+    let router = router.route("/metrics", get(super::metrics::metrics_handler));
+
+    router
         .fallback_service(ServeDir::new("static"))
         .with_state(state)
 }
@@ -30,6 +44,39 @@ async fn health_check() -> impl IntoResponse {
     }))
 }
 
+/// Readiness check: Ollama answers and the configured model is installed.
+///
+/// Returns 503 with the failing component when a dependency is down.
+async fn readiness_check(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let failure = if state.ollama.is_ready().await {
+        match state.ollama.has_model(&state.model_name).await {
+            Ok(true) => None,
+            Ok(false) => Some(("model", format!("{} is not installed", state.model_name))),
+            Err(e) => Some(("model", format!("failed to list models: {e}"))),
+        }
+    } else {
+        Some(("ollama", "server is unreachable".to_string()))
+    };
+
+    match failure {
+        None => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "status": "ready",
+                "model": state.model_name
+            })),
+        ),
+        Some((component, detail)) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({
+                "status": "unavailable",
+                "component": component,
+                "detail": detail
+            })),
+        ),
+    }
+}
+
 /// Chat completion request.
 #[derive(Debug, Deserialize)]
 pub struct ChatRequest {
@@ -48,24 +95,99 @@ pub struct ChatResponse {
     pub model: String,
 }
 
+impl ChatRequest {
+    /// Convert into chat messages, with the system prompt first when present.
+    fn into_messages(self) -> Vec<ChatMessage> {
+        let mut messages = Vec::with_capacity(2);
+        if let Some(system) = self.system_prompt {
+            messages.push(ChatMessage::system(system));
+        }
+        messages.push(ChatMessage::user(self.message));
+        messages
+    }
+}
+
 /// Handle chat completion requests.
 async fn chat_completion(
     State(state): State<Arc<AppState>>,
     Json(request): Json<ChatRequest>,
 ) -> Result<Json<ChatResponse>, (StatusCode, String)> {
-    let prompt = if let Some(system) = &request.system_prompt {
-        format!("{}\n\nUser: {}", system, request.message)
-    } else {
-        request.message.clone()
-    };
+    let messages = request.into_messages();
+    #[cfg(feature = "metrics")]
+    let started = std::time::Instant::now();
 
-    let response = state
+    let result = state
         .ollama
-        .generate_8192(&state.model_name, &prompt, "5m")
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("LLM error: {e}")))?;
+        .chat_with_stats(&state.model_name, &messages, MODEL_KEEP_ALIVE)
+        .await;
+
+    #[cfg(feature = "metrics")]
+    super::metrics::record_chat("chat", started, result.as_ref().map(|(_, stats)| stats));
+
+    let (response, _) =
+        result.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("LLM error: {e}")))?;
 
     Ok(Json(ChatResponse {
         response,
         model: state.model_name.clone(),
     }))
 }
+
+/// Handle streaming chat completion requests as server-sent events.
+///
+/// Mounted at `/api/chat/stream` rather than `/chat/stream` so the API key
+/// and rate limit checks on `/api/*` cover it.
+///
+/// Emits `{"delta": "..."}` events as tokens arrive, then `{"done": true}`, or
+/// `{"error": "..."}` if generation fails. Generation is cancelled when the
+/// client disconnects.
+async fn chat_completion_stream(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<ChatRequest>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let messages = request.into_messages();
+    let (tx, rx) = mpsc::unbounded_channel();
+    let cancel = CancellationToken::new();
+    let guard = cancel.clone().drop_guard();
+
+    tokio::spawn(async move {
+        #[cfg(feature = "metrics")]
+        let started = std::time::Instant::now();
+
+        let result = state
+            .ollama
+            .chat_stream(
+                &state.model_name,
+                &messages,
+                MODEL_KEEP_ALIVE,
+                &cancel,
+                |delta| {
+                    let _ = tx.send(json_event(&serde_json::json!({ "delta": delta })));
+                },
+            )
+            .await;
+
+        #[cfg(feature = "metrics")]
+        super::metrics::record_chat("chat_stream", started, result.as_ref());
+
+        let last = match result {
+            Ok(_) => serde_json::json!({ "done": true }),
+            Err(OllamaStarterError::Cancelled) => return,
+            Err(e) => serde_json::json!({ "error": format!("LLM error: {e}") }),
+        };
+        let _ = tx.send(json_event(&last));
+    });
+
+    // The drop guard lives as long as the response stream, so a client
+    // disconnect cancels the generation.
+    let stream = futures_util::stream::unfold((rx, guard), |(mut rx, guard)| async move {
+        rx.recv().await.map(|event| (Ok(event), (rx, guard)))
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Build an SSE event carrying a JSON payload.
+fn json_event(value: &serde_json::Value) -> Event {
+    Event::default().data(value.to_string())
+}
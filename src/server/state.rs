@@ -2,7 +2,8 @@
 
 use std::sync::Arc;
 
-use crate::llm::ollama_starter_ministral::OllamaMinistral;
+use super::rate_limit::RateLimiter;
+use crate::llm::ollama_starter_ministral::AsyncOllamaMinistral;
 
 /// Default model name.
 const DEFAULT_MODEL: &str = "ministral-3:8b-instruct-2512-q8_0";
@@ -10,23 +11,44 @@ const DEFAULT_MODEL: &str = "ministral-3:8b-instruct-2512-q8_0";
 /// Shared application state.
 pub struct AppState {
     /// Ollama client for LLM operations.
-    pub ollama: OllamaMinistral,
+    pub ollama: AsyncOllamaMinistral,
     /// Model name to use.
     pub model_name: String,
+    /// Per-client API rate limiter, `None` when disabled.
+    pub rate_limiter: Option<RateLimiter>,
 }
 
 impl AppState {
     /// Create a new application state.
     ///
+    /// `HALLDYLL_RATE_LIMIT_RPM` sets the API requests allowed per client
+    /// per minute (unset or `0` disables rate limiting), and
+    /// `HALLDYLL_TRUSTED_PROXY_HOPS` how many `X-Forwarded-For` hops to trust
+    /// when identifying clients.
+    ///
     /// # Errors
     /// Returns an error if Ollama client cannot be created.
     pub fn new() -> Result<Arc<Self>, Box<dyn std::error::Error + Send + Sync>> {
-        let ollama = OllamaMinistral::new_default()
+        let ollama = AsyncOllamaMinistral::new_default()
             .map_err(|e| format!("Failed to create Ollama client: {e}"))?;
 
-        let model_name = std::env::var("HALLDYLL_MODEL")
-            .unwrap_or_else(|_| DEFAULT_MODEL.to_string());
+        let model_name =
+            std::env::var("HALLDYLL_MODEL").unwrap_or_else(|_| DEFAULT_MODEL.to_string());
+
+        let trusted_proxy_hops = std::env::var("HALLDYLL_TRUSTED_PROXY_HOPS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(0);
+        let rate_limiter = std::env::var("HALLDYLL_RATE_LIMIT_RPM")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .filter(|&rpm| rpm > 0)
+            .map(|rpm| RateLimiter::new(rpm).with_trusted_proxy_hops(trusted_proxy_hops));
 
-        Ok(Arc::new(Self { ollama, model_name }))
+        Ok(Arc::new(Self {
+            ollama,
+            model_name,
+            rate_limiter,
+        }))
     }
 }
@@ -6,10 +6,12 @@ use std::future::Future;
 use std::process::ExitCode;
 use std::sync::Arc;
 
-use crate::server::{self, AppState};
+use crate::server::{self, AppState, ServerConfig};
 
 /// Run the server (used by `halldyll-server` binary on `RunPod`).
 ///
+/// Stops on Ctrl+C or SIGTERM after in-flight requests finish.
+///
 /// # Returns
 /// `ExitCode::SUCCESS` on graceful shutdown, `1` on failure.
 #[must_use]
@@ -35,7 +37,7 @@ pub fn run() -> ExitCode {
         }
     };
 
-    let port = get_port();
+    let config = ServerConfig::from_env();
 
     let rt = match tokio::runtime::Runtime::new() {
         Ok(rt) => rt,
@@ -45,7 +47,12 @@ pub fn run() -> ExitCode {
         }
     };
 
-    if let Err(e) = rt.block_on(server::run_server(state, port)) {
+    let result = rt.block_on(server::run_server_with_shutdown(
+        state,
+        config,
+        server::shutdown_signal(),
+    ));
+    if let Err(e) = result {
         tracing::error!("Server error: {e}");
         return ExitCode::from(1);
     }
@@ -71,20 +78,17 @@ pub fn initialize() -> Result<Arc<AppState>, Box<dyn std::error::Error + Send +
 /// Returns an error if the server fails.
 pub async fn run_server_with_shutdown<F>(
     state: Arc<AppState>,
-    port: u16,
+    config: ServerConfig,
     shutdown_signal: F,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
 where
     F: Future<Output = ()> + Send + 'static,
 {
-    server::run_server_with_shutdown(state, port, shutdown_signal).await
+    server::run_server_with_shutdown(state, config, shutdown_signal).await
 }
 
 /// Get configured server port.
 #[must_use]
 pub fn get_port() -> u16 {
-    std::env::var("HALLDYLL_PORT")
-        .ok()
-        .and_then(|p| p.parse().ok())
-        .unwrap_or(server::DEFAULT_PORT)
+    ServerConfig::from_env().port
 }